@@ -2,9 +2,9 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
-pub enum Currency { BTC, ETH, SOL, SUI, XRP }
+pub enum Currency { BTC, ETH, SOL, SUI, XRP, LN }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Amount { pub value: f64, pub currency: Currency }
@@ -20,6 +20,7 @@ pub enum GatewayError {
     #[error("invalid address: {0}")] InvalidAddress(String),
     #[error("network error: {0}")]  Network(String),
     #[error("not implemented")]     NotImplemented,
+    #[error("proof invalid: {0}")]  ProofInvalid(String),
     #[error("unknown: {0}")]        Unknown(String),
 }
 
@@ -37,6 +38,8 @@ pub struct AppliedFee {
     pub percent: f64,
     /// fee amount in the transaction currency
     pub fee_amount: f64,
+    /// estimated on-chain network fee, from a `GasOracle`, in the transaction currency
+    pub network_fee_estimate: f64,
 }
 
 #[async_trait]
@@ -48,4 +51,35 @@ pub trait Connector: Send + Sync {
     async fn tx_status(&self, tx: &TxId) -> Result<TxStatus, GatewayError>;
     async fn balance(&self, addr: &Address) -> Result<Amount, GatewayError>;
     async fn send(&self, from: &str, to: &Address, amount: Amount) -> Result<TxId, GatewayError>;
+
+    /// The next nonce/sequence number `from` should use, if this chain has the concept
+    /// and the connector can look it up (e.g. an account's pending transaction count).
+    /// Returns `None` for chains without an account nonce, or connectors that don't
+    /// support tracking one; callers fall back to plain `send`.
+    async fn next_nonce(&self, from: &str) -> Result<Option<u64>, GatewayError> {
+        let _ = from;
+        Ok(None)
+    }
+
+    /// Like `send`, but threads an explicit nonce into the submitted transaction instead
+    /// of letting the connector pick one at submission time. A connector with no concept
+    /// of an explicit nonce (or given `None`) falls back to `send`.
+    async fn send_with_nonce(
+        &self,
+        from: &str,
+        to: &Address,
+        amount: Amount,
+        nonce: Option<u64>,
+    ) -> Result<TxId, GatewayError> {
+        let _ = nonce;
+        self.send(from, to, amount).await
+    }
+}
+
+/// Estimates the current network fee for sending on a given currency's chain. Plugs
+/// into `FeeEngine::with_gas_oracle` so invoice fees reflect both the gateway's own
+/// percentage and the underlying chain's going rate.
+#[async_trait]
+pub trait GasOracle: Send + Sync {
+    async fn estimate(&self, currency: Currency) -> Result<Amount, GatewayError>;
 }
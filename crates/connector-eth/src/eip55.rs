@@ -0,0 +1,99 @@
+use ethers::types::Address as EthAddress;
+use sha3::{Digest, Keccak256};
+
+/// Validates that `addr` is a `0x`-prefixed 40 hex-digit address. An all-lowercase or
+/// all-uppercase address is accepted as-is (unchecksummed, per EIP-55 itself — the
+/// checksum is optional); a mixed-case address must match the EIP-55 checksum derived
+/// from the keccak256 hash of its lowercase hex form, or it's rejected as corrupted.
+pub fn is_valid_checksum(addr: &str) -> bool {
+    let Some(hex) = addr.strip_prefix("0x") else { return false };
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+    let all_lower = hex.chars().all(|c| !c.is_ascii_uppercase());
+    let all_upper = hex.chars().all(|c| !c.is_ascii_lowercase());
+    if all_lower || all_upper {
+        return true;
+    }
+    to_checksum_hex(hex) == hex
+}
+
+/// Renders an `ethers` address as its EIP-55 checksummed hex string, e.g. `0xAbC...`.
+pub fn to_checksum(addr: &EthAddress) -> String {
+    format!("0x{}", to_checksum_hex(&hex::encode(addr.as_bytes())))
+}
+
+fn to_checksum_hex(lowercase_hex: &str) -> String {
+    let lower = lowercase_hex.to_lowercase();
+    let hash = Keccak256::digest(lower.as_bytes());
+    lower
+        .char_indices()
+        .map(|(i, c)| {
+            if !c.is_ascii_alphabetic() {
+                return c;
+            }
+            let nibble = hash[i / 2];
+            let bit = if i % 2 == 0 { nibble >> 4 } else { nibble & 0x0f };
+            if bit >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Official EIP-55 test vector.
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    #[test]
+    fn accepts_correctly_checksummed_mixed_case() {
+        assert!(is_valid_checksum(CHECKSUMMED));
+    }
+
+    #[test]
+    fn rejects_incorrectly_checksummed_mixed_case() {
+        // Flip the case of one letter relative to the correct checksum above.
+        let corrupted = "0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        assert!(!is_valid_checksum(corrupted));
+    }
+
+    #[test]
+    fn accepts_unchecksummed_all_lowercase() {
+        assert!(is_valid_checksum(&CHECKSUMMED.to_lowercase()));
+    }
+
+    #[test]
+    fn accepts_unchecksummed_all_uppercase() {
+        let upper = format!("0x{}", CHECKSUMMED.trim_start_matches("0x").to_uppercase());
+        assert!(is_valid_checksum(&upper));
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        assert!(!is_valid_checksum("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1Be"));
+    }
+
+    #[test]
+    fn rejects_missing_0x_prefix() {
+        assert!(!is_valid_checksum(&CHECKSUMMED[2..]));
+    }
+
+    #[test]
+    fn rejects_non_hex_characters() {
+        let bogus = "0xZZAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        assert!(!is_valid_checksum(bogus));
+    }
+
+    #[test]
+    fn to_checksum_round_trips_through_is_valid_checksum() {
+        let addr: EthAddress = CHECKSUMMED.parse().unwrap();
+        let rendered = to_checksum(&addr);
+        assert_eq!(rendered, CHECKSUMMED);
+        assert!(is_valid_checksum(&rendered));
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use ethers::types::{BlockNumber, U256};
+
+use common::{Amount, Currency, GasOracle, GatewayError};
+
+use crate::{wei_to_eth, EthConnector};
+
+/// `GasOracle` backed by `eth_feeHistory`: reads a recent base fee and priority tip and
+/// reports what a standard transfer would cost at that rate.
+pub struct EthFeeHistoryOracle {
+    connector: Arc<EthConnector>,
+    gas_limit: U256,
+}
+
+impl EthFeeHistoryOracle {
+    /// `gas_limit` defaults to 21000, the cost of a plain ETH transfer.
+    pub fn new(connector: Arc<EthConnector>) -> Self {
+        Self { connector, gas_limit: U256::from(21_000u64) }
+    }
+
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = U256::from(gas_limit);
+        self
+    }
+}
+
+#[async_trait]
+impl GasOracle for EthFeeHistoryOracle {
+    async fn estimate(&self, currency: Currency) -> Result<Amount, GatewayError> {
+        if currency != Currency::ETH {
+            return Err(GatewayError::Unknown(format!(
+                "EthFeeHistoryOracle only covers ETH, not {:?}",
+                currency
+            )));
+        }
+
+        let history = self
+            .connector
+            .provider()
+            .fee_history(1u64, BlockNumber::Latest, &[50.0])
+            .await
+            .map_err(|e| GatewayError::Network(e.to_string()))?;
+
+        let base_fee = history.base_fee_per_gas.last().copied().unwrap_or_default();
+        let tip = history.reward.last().and_then(|r| r.first()).copied().unwrap_or_default();
+        let wei_per_gas = base_fee + tip;
+
+        Ok(Amount { value: wei_to_eth(wei_per_gas * self.gas_limit)?, currency: Currency::ETH })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthConnector;
+
+    #[tokio::test]
+    async fn rejects_non_eth_currency_without_touching_the_network() {
+        // `EthConnector::new` only parses the RPC URL; it doesn't connect, so this stays
+        // fully offline as long as the currency guard rejects before any RPC call.
+        let connector = Arc::new(EthConnector::new("http://localhost:8545").unwrap());
+        let oracle = EthFeeHistoryOracle::new(connector);
+
+        let err = oracle.estimate(Currency::BTC).await.unwrap_err();
+        assert!(matches!(err, GatewayError::Unknown(_)));
+    }
+}
@@ -0,0 +1,67 @@
+//! Recoverable key storage for generated deposit addresses.
+//!
+//! A deposit address is only useful if whatever gets sent to it can later be swept or
+//! spent, which means the private key behind it has to be recoverable — generating a
+//! wallet and discarding the key would make every deposit unspendable. `Keystore` is the
+//! extension point for that: swap in an HD-derivation or KMS-backed implementation for
+//! production; `InMemoryKeystore` is enough for tests/demo use.
+
+use std::collections::HashMap;
+
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::Address as EthAddress;
+use parking_lot::RwLock;
+
+use common::GatewayError;
+
+/// Persists the private key behind a generated deposit address so it can be recovered
+/// later to sweep/spend whatever was sent to it.
+pub trait Keystore: Send + Sync {
+    fn store(&self, wallet: LocalWallet) -> Result<EthAddress, GatewayError>;
+    fn wallet_for(&self, addr: EthAddress) -> Option<LocalWallet>;
+}
+
+/// In-process keystore backed by a `HashMap`; keys live only as long as the connector
+/// does. A real deployment should back deposit addresses with HD derivation or a
+/// KMS-backed `Keystore` instead.
+#[derive(Default)]
+pub struct InMemoryKeystore {
+    wallets: RwLock<HashMap<EthAddress, LocalWallet>>,
+}
+
+impl Keystore for InMemoryKeystore {
+    fn store(&self, wallet: LocalWallet) -> Result<EthAddress, GatewayError> {
+        let addr = wallet.address();
+        self.wallets.write().insert(addr, wallet);
+        Ok(addr)
+    }
+
+    fn wallet_for(&self, addr: EthAddress) -> Option<LocalWallet> {
+        self.wallets.read().get(&addr).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_wallet_is_recoverable_by_its_address() {
+        let keystore = InMemoryKeystore::default();
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let expected_addr = wallet.address();
+
+        let stored_addr = keystore.store(wallet).unwrap();
+        assert_eq!(stored_addr, expected_addr);
+
+        let recovered = keystore.wallet_for(stored_addr).expect("wallet should be recoverable");
+        assert_eq!(recovered.address(), expected_addr);
+    }
+
+    #[test]
+    fn unknown_address_is_not_recoverable() {
+        let keystore = InMemoryKeystore::default();
+        let stranger = LocalWallet::new(&mut rand::thread_rng()).address();
+        assert!(keystore.wallet_for(stranger).is_none());
+    }
+}
@@ -0,0 +1,183 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::middleware::SignerMiddleware;
+use ethers::providers::{Http, Middleware, Provider, ProviderError};
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::{Address as EthAddress, BlockNumber, Eip1559TransactionRequest, H256};
+
+use common::{Address, Amount, Connector, Currency, GatewayError, TxId, TxStatus};
+
+mod eip55;
+mod gas_oracle;
+mod keystore;
+mod proof;
+mod verified;
+
+pub use gas_oracle::EthFeeHistoryOracle;
+pub use keystore::{InMemoryKeystore, Keystore};
+pub use verified::VerifiedEthConnector;
+
+/// Converts a wei amount (as returned by the RPC node or an EIP-1186 proof) to ETH.
+pub(crate) fn wei_to_eth(wei: ethers::types::U256) -> Result<f64, GatewayError> {
+    ethers::utils::format_units(wei, "ether")
+        .map_err(|e| GatewayError::Unknown(e.to_string()))?
+        .parse()
+        .map_err(|_| GatewayError::Unknown("failed to parse balance".into()))
+}
+
+/// `Connector` backed by a real Ethereum JSON-RPC endpoint, built on ethers-rs's
+/// `Provider`/`JsonRpcClient` abstraction rather than a hand-rolled RPC client.
+pub struct EthConnector {
+    provider: Provider<Http>,
+    wallet: Option<LocalWallet>,
+    keystore: Arc<dyn Keystore>,
+}
+
+impl EthConnector {
+    /// Connect to a JSON-RPC endpoint (e.g. an Infura/Alchemy URL or a local node).
+    pub fn new(rpc_url: &str) -> Result<Self, GatewayError> {
+        let provider =
+            Provider::<Http>::try_from(rpc_url).map_err(|e| GatewayError::Network(e.to_string()))?;
+        Ok(Self { provider, wallet: None, keystore: Arc::new(InMemoryKeystore::default()) })
+    }
+
+    /// Attach a signing key so `send()` can submit transactions on this connector's behalf.
+    pub fn with_signer(mut self, signing_key: &str) -> Result<Self, GatewayError> {
+        let wallet: LocalWallet = signing_key
+            .parse()
+            .map_err(|e: ethers::signers::WalletError| GatewayError::Unknown(e.to_string()))?;
+        self.wallet = Some(wallet);
+        Ok(self)
+    }
+
+    /// Swaps in a `Keystore` backing generated deposit addresses, e.g. an HD-derivation
+    /// or KMS-backed one in place of the default in-memory store.
+    pub fn with_keystore(mut self, keystore: Arc<dyn Keystore>) -> Self {
+        self.keystore = keystore;
+        self
+    }
+
+    fn map_provider_err(e: ProviderError) -> GatewayError {
+        GatewayError::Network(e.to_string())
+    }
+
+    pub(crate) fn parse_address(raw: &str) -> Result<EthAddress, GatewayError> {
+        EthAddress::from_str(raw).map_err(|_| GatewayError::InvalidAddress(raw.to_string()))
+    }
+
+    pub(crate) fn provider(&self) -> &Provider<Http> {
+        &self.provider
+    }
+
+    /// Builds and submits the transfer, setting an explicit `.nonce(...)` on the tx when
+    /// one is given instead of letting `fill_transaction` pick one at submission time.
+    async fn send_inner(
+        &self,
+        to: &Address,
+        amount: Amount,
+        nonce: Option<u64>,
+    ) -> Result<TxId, GatewayError> {
+        let wallet = self
+            .wallet
+            .clone()
+            .ok_or_else(|| GatewayError::Unknown("no signing key configured for this connector".into()))?;
+        let to_addr = Self::parse_address(&to.address)?;
+        let value = ethers::utils::parse_ether(amount.value).map_err(|e| GatewayError::Unknown(e.to_string()))?;
+
+        let chain_id = self.provider.get_chainid().await.map_err(Self::map_provider_err)?.as_u64();
+        let client = SignerMiddleware::new(self.provider.clone(), wallet.with_chain_id(chain_id));
+
+        let mut tx = Eip1559TransactionRequest::new().to(to_addr).value(value);
+        if let Some(n) = nonce {
+            tx = tx.nonce(n);
+        }
+        let pending = client
+            .send_transaction(tx, None)
+            .await
+            .map_err(|e| GatewayError::Network(e.to_string()))?;
+        Ok(TxId(format!("{:#x}", pending.tx_hash())))
+    }
+}
+
+#[async_trait]
+impl Connector for EthConnector {
+    fn currency(&self) -> Currency {
+        Currency::ETH
+    }
+
+    async fn validate_address(&self, addr: &str) -> Result<bool, GatewayError> {
+        Ok(eip55::is_valid_checksum(addr))
+    }
+
+    async fn new_deposit_address(&self) -> Result<Address, GatewayError> {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let addr = self.keystore.store(wallet)?;
+        Ok(Address { address: eip55::to_checksum(&addr), currency: Currency::ETH })
+    }
+
+    async fn create_payment_request(&self, amount: Amount) -> Result<(Address, String), GatewayError> {
+        let addr = self.new_deposit_address().await?;
+        let _ = amount;
+        Ok((addr, uuid::Uuid::new_v4().to_string()))
+    }
+
+    async fn tx_status(&self, tx: &TxId) -> Result<TxStatus, GatewayError> {
+        let hash = H256::from_str(&tx.0)
+            .map_err(|_| GatewayError::Unknown(format!("invalid tx hash: {}", tx.0)))?;
+        let Some(receipt) = self
+            .provider
+            .get_transaction_receipt(hash)
+            .await
+            .map_err(Self::map_provider_err)?
+        else {
+            return Ok(TxStatus::Pending);
+        };
+
+        match receipt.status.map(|s| s.as_u64()) {
+            Some(1) => {
+                let latest = self.provider.get_block_number().await.map_err(Self::map_provider_err)?;
+                let confirmations = receipt
+                    .block_number
+                    .map(|bn| latest.saturating_sub(bn).as_u32())
+                    .unwrap_or(0);
+                Ok(TxStatus::Confirmed(confirmations))
+            }
+            Some(0) => Ok(TxStatus::Failed("transaction reverted".into())),
+            _ => Ok(TxStatus::Pending),
+        }
+    }
+
+    async fn balance(&self, addr: &Address) -> Result<Amount, GatewayError> {
+        let eth_addr = Self::parse_address(&addr.address)?;
+        let wei = self.provider.get_balance(eth_addr, None).await.map_err(Self::map_provider_err)?;
+        Ok(Amount { value: wei_to_eth(wei)?, currency: Currency::ETH })
+    }
+
+    async fn send(&self, _from: &str, to: &Address, amount: Amount) -> Result<TxId, GatewayError> {
+        self.send_inner(to, amount, None).await
+    }
+
+    async fn next_nonce(&self, from: &str) -> Result<Option<u64>, GatewayError> {
+        let addr = Self::parse_address(from)?;
+        // `Pending` includes transactions already broadcast but not yet mined, which is
+        // what a local nonce counter needs to seed itself from.
+        let count = self
+            .provider
+            .get_transaction_count(addr, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(Self::map_provider_err)?;
+        Ok(Some(count.as_u64()))
+    }
+
+    async fn send_with_nonce(
+        &self,
+        _from: &str,
+        to: &Address,
+        amount: Amount,
+        nonce: Option<u64>,
+    ) -> Result<TxId, GatewayError> {
+        self.send_inner(to, amount, nonce).await
+    }
+}
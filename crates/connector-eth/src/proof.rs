@@ -0,0 +1,276 @@
+//! EIP-1186 account proof verification.
+//!
+//! `eth_getProof` hands back the chain of Merkle-Patricia trie nodes from the state
+//! root down to an account's leaf. Rather than trusting whatever balance the RPC node
+//! reports, we walk that chain ourselves: each node must hash to the reference its
+//! parent pointed at, and the final leaf must decode to the account whose balance we
+//! report. This is the same trust model a light client (e.g. Helios) uses for
+//! execution-layer state — the only thing we have to trust out-of-band is the
+//! `stateRoot` itself.
+
+use ethers::types::{EIP1186ProofResponse, H256, U256};
+use rlp::Rlp;
+use sha3::{Digest, Keccak256};
+
+use common::GatewayError;
+
+/// Verifies `proof` against `state_root`, returning the account's `(balance, nonce)`.
+///
+/// A verified *exclusion* proof — one that proves the account simply doesn't exist yet
+/// at this state root, e.g. a freshly generated, not-yet-funded deposit address — is not
+/// an error: it resolves to `(0, 0)`. Only a proof whose nodes don't actually hash/shape
+/// up (a corrupt or lying prover) is rejected with `GatewayError::ProofInvalid`.
+///
+/// This covers the common case where every trie node on the path hashes to 32+ bytes
+/// (true for any non-trivial state trie); a node short enough to be inlined by value
+/// instead of referenced by hash is not supported and is reported as `ProofInvalid`
+/// rather than risking a panic on the length mismatch.
+pub fn verify_account(
+    proof: &EIP1186ProofResponse,
+    state_root: H256,
+) -> Result<(U256, U256), GatewayError> {
+    let key = Keccak256::digest(proof.address.as_bytes());
+    let mut nibbles = to_nibbles(&key);
+    let mut expected_hash = state_root;
+
+    for node_rlp in &proof.account_proof {
+        let node_bytes: &[u8] = node_rlp.as_ref();
+        let hash = H256::from_slice(&Keccak256::digest(node_bytes));
+        if hash != expected_hash {
+            return Err(GatewayError::ProofInvalid(format!(
+                "trie node hash mismatch: expected {:#x}, got {:#x}",
+                expected_hash, hash
+            )));
+        }
+
+        let rlp = Rlp::new(node_bytes);
+        let item_count = rlp.item_count().map_err(rlp_err)?;
+
+        if item_count == 17 {
+            if nibbles.is_empty() {
+                let value = rlp.at(16).map_err(rlp_err)?.data().map_err(rlp_err)?;
+                return decode_account(value);
+            }
+            let idx = nibbles.remove(0) as usize;
+            let child = rlp.at(idx).map_err(rlp_err)?.data().map_err(rlp_err)?;
+            if child.is_empty() {
+                // Empty branch slot on the account's own path: a verified exclusion
+                // proof, not a corrupt one — the trie simply has nothing there yet.
+                return Ok((U256::zero(), U256::zero()));
+            }
+            expected_hash = as_node_hash(child)?;
+        } else if item_count == 2 {
+            let path_bytes = rlp.at(0).map_err(rlp_err)?.data().map_err(rlp_err)?;
+            let (partial, is_leaf) = hex_prefix_decode(path_bytes);
+            if nibbles.len() < partial.len() || nibbles[..partial.len()] != partial[..] {
+                // The proof terminates with a node whose encoded path diverges from the
+                // account key: exactly how Ethereum proves non-inclusion.
+                return Ok((U256::zero(), U256::zero()));
+            }
+            nibbles.drain(..partial.len());
+
+            let value = rlp.at(1).map_err(rlp_err)?.data().map_err(rlp_err)?;
+            if is_leaf {
+                return decode_account(value);
+            }
+            expected_hash = as_node_hash(value)?;
+        } else {
+            return Err(GatewayError::ProofInvalid(format!(
+                "unexpected trie node shape ({item_count} items)"
+            )));
+        }
+    }
+
+    Err(GatewayError::ProofInvalid(
+        "proof ended before reaching an account leaf or a verified exclusion".into(),
+    ))
+}
+
+/// Interprets `bytes` as a trie node reference, which EIP-1186 proofs always encode as a
+/// 32-byte keccak256 hash. A shorter reference means the node was inlined by value
+/// instead — a shape this verifier doesn't support — so that's reported as
+/// `ProofInvalid` rather than panicking on the length mismatch.
+fn as_node_hash(bytes: &[u8]) -> Result<H256, GatewayError> {
+    if bytes.len() != 32 {
+        return Err(GatewayError::ProofInvalid(
+            "trie node is referenced by value (embedded node), which is not supported".into(),
+        ));
+    }
+    Ok(H256::from_slice(bytes))
+}
+
+fn decode_account(rlp_bytes: &[u8]) -> Result<(U256, U256), GatewayError> {
+    if rlp_bytes.is_empty() {
+        // Verified exclusion proof: the leaf slot exists in shape but carries no
+        // account, i.e. a not-yet-funded address.
+        return Ok((U256::zero(), U256::zero()));
+    }
+    let rlp = Rlp::new(rlp_bytes);
+    let nonce: U256 = rlp.val_at(0).map_err(rlp_err)?;
+    let balance: U256 = rlp.val_at(1).map_err(rlp_err)?;
+    Ok((balance, nonce))
+}
+
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+/// Decodes a hex-prefix encoded partial path (Ethereum's MPT nibble-compaction scheme),
+/// returning the remaining nibbles and whether this node terminates in a leaf.
+fn hex_prefix_decode(encoded: &[u8]) -> (Vec<u8>, bool) {
+    let Some(&first) = encoded.first() else { return (Vec::new(), false) };
+    let is_leaf = first & 0x20 != 0;
+    let is_odd = first & 0x10 != 0;
+    let mut nibbles = Vec::new();
+    if is_odd {
+        nibbles.push(first & 0x0f);
+    }
+    for &b in &encoded[1..] {
+        nibbles.push(b >> 4);
+        nibbles.push(b & 0x0f);
+    }
+    (nibbles, is_leaf)
+}
+
+fn rlp_err(e: rlp::DecoderError) -> GatewayError {
+    GatewayError::ProofInvalid(format!("malformed trie node: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Address as EthAddress, Bytes};
+    use rlp::RlpStream;
+
+    fn test_addr() -> EthAddress {
+        EthAddress::repeat_byte(0x11)
+    }
+
+    fn hex_prefix_encode(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let mut flag = if is_leaf { 0x20 } else { 0x00 };
+        let mut iter = nibbles.iter().copied();
+        let mut out = Vec::new();
+        if nibbles.len() % 2 == 1 {
+            flag |= 0x10 | iter.next().unwrap();
+        }
+        out.push(flag);
+        let rest: Vec<u8> = iter.collect();
+        for pair in rest.chunks(2) {
+            out.push((pair[0] << 4) | pair[1]);
+        }
+        out
+    }
+
+    fn encode_account(nonce: u64, balance: u64) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&U256::from(nonce));
+        stream.append(&U256::from(balance));
+        stream.out().to_vec()
+    }
+
+    fn single_leaf_proof(nonce: u64, balance: u64) -> (EIP1186ProofResponse, H256) {
+        let key = Keccak256::digest(test_addr().as_bytes());
+        let nibbles = to_nibbles(&key);
+        let path = hex_prefix_encode(&nibbles, true);
+        let account = encode_account(nonce, balance);
+
+        let mut leaf = RlpStream::new_list(2);
+        leaf.append(&path);
+        leaf.append(&account);
+        let leaf_bytes = leaf.out().to_vec();
+        let root = H256::from_slice(&Keccak256::digest(&leaf_bytes));
+
+        (proof_response(vec![leaf_bytes]), root)
+    }
+
+    fn proof_response(account_proof: Vec<Vec<u8>>) -> EIP1186ProofResponse {
+        EIP1186ProofResponse {
+            address: test_addr(),
+            balance: U256::zero(),
+            code_hash: H256::zero(),
+            nonce: U256::zero(),
+            storage_hash: H256::zero(),
+            account_proof: account_proof.into_iter().map(Bytes::from).collect(),
+            storage_proof: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn verifies_existing_account_leaf() {
+        let (proof, root) = single_leaf_proof(7, 1_000_000);
+        let (balance, nonce) = verify_account(&proof, root).expect("valid proof");
+        assert_eq!(balance, U256::from(1_000_000u64));
+        assert_eq!(nonce, U256::from(7u64));
+    }
+
+    #[test]
+    fn empty_branch_slot_is_a_verified_exclusion_not_an_error() {
+        // A branch node with every child slot empty: whichever nibble the account's key
+        // selects, there's nothing there, i.e. the account doesn't exist yet.
+        let mut branch = RlpStream::new_list(17);
+        for _ in 0..17 {
+            branch.append_empty_data();
+        }
+        let branch_bytes = branch.out().to_vec();
+        let root = H256::from_slice(&Keccak256::digest(&branch_bytes));
+        let proof = proof_response(vec![branch_bytes]);
+
+        let (balance, nonce) = verify_account(&proof, root).expect("exclusion proof should verify");
+        assert_eq!(balance, U256::zero());
+        assert_eq!(nonce, U256::zero());
+    }
+
+    #[test]
+    fn diverging_leaf_path_is_a_verified_exclusion_not_an_error() {
+        // A leaf whose encoded path differs from the target account's key from the very
+        // first nibble: the standard way Ethereum proves an account isn't in the trie.
+        let mut other_nibbles = to_nibbles(&Keccak256::digest(test_addr().as_bytes()));
+        other_nibbles[0] = (other_nibbles[0] + 1) % 16;
+        let path = hex_prefix_encode(&other_nibbles, true);
+        let account = encode_account(1, 1);
+
+        let mut leaf = RlpStream::new_list(2);
+        leaf.append(&path);
+        leaf.append(&account);
+        let leaf_bytes = leaf.out().to_vec();
+        let root = H256::from_slice(&Keccak256::digest(&leaf_bytes));
+        let proof = proof_response(vec![leaf_bytes]);
+
+        let (balance, nonce) = verify_account(&proof, root).expect("exclusion proof should verify");
+        assert_eq!(balance, U256::zero());
+        assert_eq!(nonce, U256::zero());
+    }
+
+    #[test]
+    fn hash_mismatch_is_rejected_as_invalid() {
+        let (proof, _root) = single_leaf_proof(1, 1);
+        let wrong_root = H256::repeat_byte(0xff);
+        let err = verify_account(&proof, wrong_root).unwrap_err();
+        assert!(matches!(err, GatewayError::ProofInvalid(_)));
+    }
+
+    #[test]
+    fn embedded_node_reference_is_rejected_not_panicking() {
+        // A branch child slot that's non-empty but shorter than the 32-byte hash every
+        // EIP-1186 proof node reference should be: an unsupported "inlined by value"
+        // node. This must report ProofInvalid, not panic on the length mismatch.
+        let key = Keccak256::digest(test_addr().as_bytes());
+        let nibbles = to_nibbles(&key);
+        let idx = nibbles[0] as usize;
+
+        let mut branch = RlpStream::new_list(17);
+        for i in 0..17 {
+            if i == idx {
+                branch.append(&vec![0xabu8; 5]);
+            } else {
+                branch.append_empty_data();
+            }
+        }
+        let branch_bytes = branch.out().to_vec();
+        let root = H256::from_slice(&Keccak256::digest(&branch_bytes));
+        let proof = proof_response(vec![branch_bytes]);
+
+        let err = verify_account(&proof, root).unwrap_err();
+        assert!(matches!(err, GatewayError::ProofInvalid(_)));
+    }
+}
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use ethers::types::{BlockId, H256};
+
+use common::{Address, Amount, Connector, Currency, GatewayError, TxId, TxStatus};
+
+use crate::{proof, wei_to_eth, EthConnector};
+
+/// Wraps an `EthConnector` so that `balance()` is backed by a verified EIP-1186
+/// (`eth_getProof`) Merkle-Patricia proof instead of a bare `eth_getBalance` answer,
+/// giving merchants cryptographic assurance rather than blind trust in a single RPC
+/// node. The caller pins both the `state_root` to verify against and the `block` whose
+/// state that root belongs to (so `eth_getProof` knows where to query) — typically a
+/// recent finalized block obtained out-of-band, e.g. from a consensus-layer light
+/// client.
+pub struct VerifiedEthConnector {
+    inner: EthConnector,
+    state_root: H256,
+    block: BlockId,
+}
+
+impl VerifiedEthConnector {
+    pub fn new(inner: EthConnector, state_root: H256, block: BlockId) -> Self {
+        Self { inner, state_root, block }
+    }
+}
+
+#[async_trait]
+impl Connector for VerifiedEthConnector {
+    fn currency(&self) -> Currency {
+        self.inner.currency()
+    }
+
+    async fn validate_address(&self, addr: &str) -> Result<bool, GatewayError> {
+        self.inner.validate_address(addr).await
+    }
+
+    async fn new_deposit_address(&self) -> Result<Address, GatewayError> {
+        self.inner.new_deposit_address().await
+    }
+
+    async fn create_payment_request(&self, amount: Amount) -> Result<(Address, String), GatewayError> {
+        self.inner.create_payment_request(amount).await
+    }
+
+    async fn tx_status(&self, tx: &TxId) -> Result<TxStatus, GatewayError> {
+        self.inner.tx_status(tx).await
+    }
+
+    async fn balance(&self, addr: &Address) -> Result<Amount, GatewayError> {
+        let eth_addr = EthConnector::parse_address(&addr.address)?;
+        let response = self
+            .inner
+            .provider()
+            .get_proof(eth_addr, Vec::new(), Some(self.block))
+            .await
+            .map_err(|e| GatewayError::Network(e.to_string()))?;
+
+        let (balance_wei, _nonce) = proof::verify_account(&response, self.state_root)?;
+        Ok(Amount { value: wei_to_eth(balance_wei)?, currency: Currency::ETH })
+    }
+
+    async fn send(&self, from: &str, to: &Address, amount: Amount) -> Result<TxId, GatewayError> {
+        self.inner.send(from, to, amount).await
+    }
+
+    async fn next_nonce(&self, from: &str) -> Result<Option<u64>, GatewayError> {
+        self.inner.next_nonce(from).await
+    }
+
+    async fn send_with_nonce(
+        &self,
+        from: &str,
+        to: &Address,
+        amount: Amount,
+        nonce: Option<u64>,
+    ) -> Result<TxId, GatewayError> {
+        self.inner.send_with_nonce(from, to, amount, nonce).await
+    }
+}
@@ -0,0 +1,12 @@
+//! Composable `Connector` middleware, layered the way ethers-rs layers its `Middleware`
+//! stack: each wrapper holds an inner `Arc<dyn Connector>` and delegates to it, so any
+//! number of these can be stacked around a real connector before it's registered with
+//! a `Registry`.
+
+mod logging;
+mod nonce_manager;
+mod retry;
+
+pub use logging::LoggingConnector;
+pub use nonce_manager::NonceManagerConnector;
+pub use retry::RetryConnector;
@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{info_span, Instrument};
+
+use common::{Address, Amount, Connector, Currency, GatewayError, TxId, TxStatus};
+
+/// Wraps an inner `Connector` and emits a `tracing` span around each call, so connector
+/// activity shows up under whatever subscriber the host process has configured.
+pub struct LoggingConnector {
+    inner: Arc<dyn Connector>,
+}
+
+impl LoggingConnector {
+    pub fn new(inner: Arc<dyn Connector>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl Connector for LoggingConnector {
+    fn currency(&self) -> Currency {
+        self.inner.currency()
+    }
+
+    async fn validate_address(&self, addr: &str) -> Result<bool, GatewayError> {
+        let currency = self.inner.currency();
+        self.inner
+            .validate_address(addr)
+            .instrument(info_span!("connector.validate_address", ?currency, addr))
+            .await
+    }
+
+    async fn new_deposit_address(&self) -> Result<Address, GatewayError> {
+        let currency = self.inner.currency();
+        self.inner
+            .new_deposit_address()
+            .instrument(info_span!("connector.new_deposit_address", ?currency))
+            .await
+    }
+
+    async fn create_payment_request(&self, amount: Amount) -> Result<(Address, String), GatewayError> {
+        let currency = self.inner.currency();
+        self.inner
+            .create_payment_request(amount)
+            .instrument(info_span!("connector.create_payment_request", ?currency))
+            .await
+    }
+
+    async fn tx_status(&self, tx: &TxId) -> Result<TxStatus, GatewayError> {
+        let currency = self.inner.currency();
+        self.inner
+            .tx_status(tx)
+            .instrument(info_span!("connector.tx_status", ?currency, tx = %tx.0))
+            .await
+    }
+
+    async fn balance(&self, addr: &Address) -> Result<Amount, GatewayError> {
+        let currency = self.inner.currency();
+        self.inner
+            .balance(addr)
+            .instrument(info_span!("connector.balance", ?currency, addr = %addr.address))
+            .await
+    }
+
+    async fn send(&self, from: &str, to: &Address, amount: Amount) -> Result<TxId, GatewayError> {
+        let currency = self.inner.currency();
+        self.inner
+            .send(from, to, amount)
+            .instrument(info_span!("connector.send", ?currency, from, to = %to.address))
+            .await
+    }
+
+    async fn next_nonce(&self, from: &str) -> Result<Option<u64>, GatewayError> {
+        let currency = self.inner.currency();
+        self.inner
+            .next_nonce(from)
+            .instrument(info_span!("connector.next_nonce", ?currency, from))
+            .await
+    }
+
+    async fn send_with_nonce(
+        &self,
+        from: &str,
+        to: &Address,
+        amount: Amount,
+        nonce: Option<u64>,
+    ) -> Result<TxId, GatewayError> {
+        let currency = self.inner.currency();
+        self.inner
+            .send_with_nonce(from, to, amount, nonce)
+            .instrument(info_span!("connector.send_with_nonce", ?currency, from, to = %to.address, ?nonce))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeConnector;
+
+    #[async_trait]
+    impl Connector for FakeConnector {
+        fn currency(&self) -> Currency {
+            Currency::BTC
+        }
+        async fn validate_address(&self, addr: &str) -> Result<bool, GatewayError> {
+            Ok(addr == "valid")
+        }
+        async fn new_deposit_address(&self) -> Result<Address, GatewayError> {
+            Ok(Address { address: "addr".into(), currency: Currency::BTC })
+        }
+        async fn create_payment_request(&self, amount: Amount) -> Result<(Address, String), GatewayError> {
+            Ok((Address { address: "addr".into(), currency: Currency::BTC }, format!("inv-{}", amount.value)))
+        }
+        async fn tx_status(&self, _tx: &TxId) -> Result<TxStatus, GatewayError> {
+            Ok(TxStatus::Confirmed(3))
+        }
+        async fn balance(&self, addr: &Address) -> Result<Amount, GatewayError> {
+            Ok(Amount { value: 42.0, currency: addr.currency })
+        }
+        async fn send(&self, _from: &str, _to: &Address, _amount: Amount) -> Result<TxId, GatewayError> {
+            Ok(TxId("tx-hash".into()))
+        }
+        async fn next_nonce(&self, _from: &str) -> Result<Option<u64>, GatewayError> {
+            Ok(Some(7))
+        }
+        async fn send_with_nonce(
+            &self,
+            _from: &str,
+            _to: &Address,
+            _amount: Amount,
+            nonce: Option<u64>,
+        ) -> Result<TxId, GatewayError> {
+            Ok(TxId(format!("tx-nonce-{}", nonce.expect("nonce should be forwarded"))))
+        }
+    }
+
+    #[tokio::test]
+    async fn delegates_next_nonce_and_send_with_nonce_to_inner() {
+        let logging = LoggingConnector::new(Arc::new(FakeConnector));
+
+        assert_eq!(logging.next_nonce("0xabc").await.unwrap(), Some(7));
+
+        let to = Address { address: "dest".into(), currency: Currency::BTC };
+        let tx = logging
+            .send_with_nonce("0xabc", &to, Amount { value: 1.0, currency: Currency::BTC }, Some(42))
+            .await
+            .unwrap();
+        assert_eq!(tx.0, "tx-nonce-42");
+    }
+
+    #[tokio::test]
+    async fn delegates_results_unchanged() {
+        let logging = LoggingConnector::new(Arc::new(FakeConnector));
+
+        assert_eq!(logging.currency(), Currency::BTC);
+        assert!(logging.validate_address("valid").await.unwrap());
+        assert!(!logging.validate_address("nope").await.unwrap());
+
+        let addr = Address { address: "dest".into(), currency: Currency::BTC };
+        let (_, invoice_id) = logging
+            .create_payment_request(Amount { value: 2.5, currency: Currency::BTC })
+            .await
+            .unwrap();
+        assert_eq!(invoice_id, "inv-2.5");
+
+        assert!(matches!(logging.tx_status(&TxId("tx".into())).await.unwrap(), TxStatus::Confirmed(3)));
+
+        let balance = logging.balance(&addr).await.unwrap();
+        assert_eq!(balance.value, 42.0);
+
+        let tx = logging.send("from", &addr, Amount { value: 1.0, currency: Currency::BTC }).await.unwrap();
+        assert_eq!(tx.0, "tx-hash");
+    }
+}
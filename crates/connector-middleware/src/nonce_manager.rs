@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use common::{Address, Amount, Connector, Currency, GatewayError, TxId, TxStatus};
+
+/// Wraps an inner `Connector` and maintains a real local nonce counter per source
+/// address, so back-to-back `send()` calls don't both read the same on-chain nonce
+/// before either has been mined.
+///
+/// Serializing concurrent calls alone isn't enough: the inner connector's nonce still
+/// comes from the chain (e.g. `eth_getTransactionCount`), which doesn't advance until a
+/// prior send is mined, so two *sequential* sends from the same address would collide
+/// just as badly as two concurrent ones. Instead this middleware fetches the nonce once
+/// per address (via `Connector::next_nonce`), hands it to the inner connector explicitly
+/// (via `Connector::send_with_nonce`), and increments its local copy on success —
+/// re-fetching from the chain only the first time, or after a failed send invalidates
+/// the cached value. Distinct source addresses proceed in parallel.
+pub struct NonceManagerConnector {
+    inner: Arc<dyn Connector>,
+    locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+    next_nonces: Mutex<HashMap<String, u64>>,
+}
+
+impl NonceManagerConnector {
+    pub fn new(inner: Arc<dyn Connector>) -> Self {
+        Self { inner, locks: Mutex::new(HashMap::new()), next_nonces: Mutex::new(HashMap::new()) }
+    }
+
+    async fn lock_for(&self, from: &str) -> Arc<Mutex<()>> {
+        let mut locks = self.locks.lock().await;
+        locks.entry(from.to_string()).or_insert_with(|| Arc::new(Mutex::new(()))).clone()
+    }
+
+    /// The nonce to use for `from`'s next send: the cached value if we have one,
+    /// otherwise whatever the inner connector reports (fetched once and cached).
+    async fn next_nonce_for(&self, from: &str) -> Result<Option<u64>, GatewayError> {
+        if let Some(n) = self.next_nonces.lock().await.get(from).copied() {
+            return Ok(Some(n));
+        }
+        let fetched = self.inner.next_nonce(from).await?;
+        if let Some(n) = fetched {
+            self.next_nonces.lock().await.insert(from.to_string(), n);
+        }
+        Ok(fetched)
+    }
+
+    async fn advance_nonce(&self, from: &str, nonce: Option<u64>) {
+        if let Some(n) = nonce {
+            self.next_nonces.lock().await.insert(from.to_string(), n + 1);
+        }
+    }
+
+    /// Drops the cached nonce so the next send re-fetches from the chain, rather than
+    /// staying stuck on a nonce that a failed/reverted send never consumed.
+    async fn invalidate_nonce(&self, from: &str) {
+        self.next_nonces.lock().await.remove(from);
+    }
+}
+
+#[async_trait]
+impl Connector for NonceManagerConnector {
+    fn currency(&self) -> Currency {
+        self.inner.currency()
+    }
+
+    async fn validate_address(&self, addr: &str) -> Result<bool, GatewayError> {
+        self.inner.validate_address(addr).await
+    }
+
+    async fn new_deposit_address(&self) -> Result<Address, GatewayError> {
+        self.inner.new_deposit_address().await
+    }
+
+    async fn create_payment_request(&self, amount: Amount) -> Result<(Address, String), GatewayError> {
+        self.inner.create_payment_request(amount).await
+    }
+
+    async fn tx_status(&self, tx: &TxId) -> Result<TxStatus, GatewayError> {
+        self.inner.tx_status(tx).await
+    }
+
+    async fn balance(&self, addr: &Address) -> Result<Amount, GatewayError> {
+        self.inner.balance(addr).await
+    }
+
+    async fn send(&self, from: &str, to: &Address, amount: Amount) -> Result<TxId, GatewayError> {
+        let per_address = self.lock_for(from).await;
+        let _guard = per_address.lock().await;
+
+        let nonce = self.next_nonce_for(from).await?;
+        match self.inner.send_with_nonce(from, to, amount, nonce).await {
+            Ok(tx) => {
+                self.advance_nonce(from, nonce).await;
+                Ok(tx)
+            }
+            Err(e) => {
+                self.invalidate_nonce(from).await;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::Mutex as StdMutex;
+
+    /// Mimics a chain RPC whose reported nonce doesn't advance until a submitted
+    /// transaction is mined — exactly the condition that makes plain serialization
+    /// insufficient to prevent nonce collisions across sequential sends.
+    struct StaleChainConnector {
+        chain_nonce: AtomicU64,
+        fail_next_send: AtomicBool,
+        next_nonce_calls: AtomicU64,
+        used_nonces: StdMutex<Vec<Option<u64>>>,
+    }
+
+    impl StaleChainConnector {
+        fn new(chain_nonce: u64) -> Self {
+            Self {
+                chain_nonce: AtomicU64::new(chain_nonce),
+                fail_next_send: AtomicBool::new(false),
+                next_nonce_calls: AtomicU64::new(0),
+                used_nonces: StdMutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Connector for StaleChainConnector {
+        fn currency(&self) -> Currency {
+            Currency::ETH
+        }
+        async fn validate_address(&self, _addr: &str) -> Result<bool, GatewayError> {
+            Ok(true)
+        }
+        async fn new_deposit_address(&self) -> Result<Address, GatewayError> {
+            Ok(Address { address: "addr".into(), currency: Currency::ETH })
+        }
+        async fn create_payment_request(&self, _amount: Amount) -> Result<(Address, String), GatewayError> {
+            Ok((Address { address: "addr".into(), currency: Currency::ETH }, "inv".into()))
+        }
+        async fn tx_status(&self, _tx: &TxId) -> Result<TxStatus, GatewayError> {
+            Ok(TxStatus::Pending)
+        }
+        async fn balance(&self, _addr: &Address) -> Result<Amount, GatewayError> {
+            Ok(Amount { value: 0.0, currency: Currency::ETH })
+        }
+        async fn send(&self, from: &str, to: &Address, amount: Amount) -> Result<TxId, GatewayError> {
+            self.send_with_nonce(from, to, amount, None).await
+        }
+        async fn next_nonce(&self, _from: &str) -> Result<Option<u64>, GatewayError> {
+            self.next_nonce_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Some(self.chain_nonce.load(Ordering::SeqCst)))
+        }
+        async fn send_with_nonce(
+            &self,
+            _from: &str,
+            _to: &Address,
+            _amount: Amount,
+            nonce: Option<u64>,
+        ) -> Result<TxId, GatewayError> {
+            self.used_nonces.lock().unwrap().push(nonce);
+            if self.fail_next_send.swap(false, Ordering::SeqCst) {
+                return Err(GatewayError::Network("simulated broadcast failure".into()));
+            }
+            Ok(TxId(format!("tx-{nonce:?}")))
+        }
+    }
+
+    fn dest() -> Address {
+        Address { address: "0xdest".into(), currency: Currency::ETH }
+    }
+
+    #[tokio::test]
+    async fn sequential_sends_use_increasing_nonces_despite_a_stale_chain_nonce() {
+        let inner = Arc::new(StaleChainConnector::new(5));
+        let nm = NonceManagerConnector::new(inner.clone());
+
+        for _ in 0..3 {
+            nm.send("0xfrom", &dest(), Amount { value: 1.0, currency: Currency::ETH }).await.unwrap();
+        }
+
+        assert_eq!(*inner.used_nonces.lock().unwrap(), vec![Some(5), Some(6), Some(7)]);
+        // The chain nonce was only consulted once; the rest came from the local counter.
+        assert_eq!(inner.next_nonce_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn distinct_addresses_track_independent_nonce_sequences() {
+        let inner = Arc::new(StaleChainConnector::new(0));
+        let nm = NonceManagerConnector::new(inner.clone());
+
+        nm.send("0xfrom-a", &dest(), Amount { value: 1.0, currency: Currency::ETH }).await.unwrap();
+        nm.send("0xfrom-b", &dest(), Amount { value: 1.0, currency: Currency::ETH }).await.unwrap();
+        nm.send("0xfrom-a", &dest(), Amount { value: 1.0, currency: Currency::ETH }).await.unwrap();
+
+        assert_eq!(*inner.used_nonces.lock().unwrap(), vec![Some(0), Some(0), Some(1)]);
+    }
+
+    #[tokio::test]
+    async fn a_failed_send_invalidates_the_cached_nonce_so_the_next_attempt_refetches() {
+        let inner = Arc::new(StaleChainConnector::new(10));
+        inner.fail_next_send.store(true, Ordering::SeqCst);
+        let nm = NonceManagerConnector::new(inner.clone());
+
+        let first = nm.send("0xfrom", &dest(), Amount { value: 1.0, currency: Currency::ETH }).await;
+        assert!(first.is_err());
+
+        let second = nm.send("0xfrom", &dest(), Amount { value: 1.0, currency: Currency::ETH }).await;
+        assert!(second.is_ok());
+
+        assert_eq!(*inner.used_nonces.lock().unwrap(), vec![Some(10), Some(10)]);
+        assert_eq!(inner.next_nonce_calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Guards against `RetryConnector`/`LoggingConnector` silently falling back to the
+    /// `Connector` trait's `next_nonce`/`send_with_nonce` defaults instead of forwarding
+    /// to the innermost connector — stacked exactly as `main()` wires the real ETH chain:
+    /// `NonceManagerConnector::new(RetryConnector::new(LoggingConnector::new(eth_connector)))`.
+    #[tokio::test]
+    async fn nonce_tracking_survives_a_multi_layer_middleware_stack() {
+        let inner = Arc::new(StaleChainConnector::new(5));
+        let stack: Arc<dyn Connector> = Arc::new(crate::RetryConnector::new(Arc::new(crate::LoggingConnector::new(inner.clone()))));
+        let nm = NonceManagerConnector::new(stack);
+
+        for _ in 0..3 {
+            nm.send("0xfrom", &dest(), Amount { value: 1.0, currency: Currency::ETH }).await.unwrap();
+        }
+
+        assert_eq!(*inner.used_nonces.lock().unwrap(), vec![Some(5), Some(6), Some(7)]);
+        assert_eq!(inner.next_nonce_calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,208 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::time::sleep;
+
+use common::{Address, Amount, Connector, Currency, GatewayError, TxId, TxStatus};
+
+/// Wraps an inner `Connector` and retries calls that fail with `GatewayError::Network`,
+/// backing off exponentially between attempts. Any other error kind is treated as
+/// terminal and returned on the first try.
+pub struct RetryConnector {
+    inner: Arc<dyn Connector>,
+    max_attempts: u32,
+    base_delay: Duration,
+}
+
+impl RetryConnector {
+    pub fn new(inner: Arc<dyn Connector>) -> Self {
+        Self { inner, max_attempts: 3, base_delay: Duration::from_millis(200) }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    async fn retry<T, F, Fut>(&self, op: F) -> Result<T, GatewayError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, GatewayError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Ok(v) => return Ok(v),
+                Err(GatewayError::Network(msg)) if attempt + 1 < self.max_attempts => {
+                    sleep(self.base_delay * 2u32.pow(attempt)).await;
+                    attempt += 1;
+                    let _ = &msg;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Connector for RetryConnector {
+    fn currency(&self) -> Currency {
+        self.inner.currency()
+    }
+
+    async fn validate_address(&self, addr: &str) -> Result<bool, GatewayError> {
+        self.retry(|| self.inner.validate_address(addr)).await
+    }
+
+    async fn new_deposit_address(&self) -> Result<Address, GatewayError> {
+        self.retry(|| self.inner.new_deposit_address()).await
+    }
+
+    async fn create_payment_request(&self, amount: Amount) -> Result<(Address, String), GatewayError> {
+        self.retry(|| self.inner.create_payment_request(amount.clone())).await
+    }
+
+    async fn tx_status(&self, tx: &TxId) -> Result<TxStatus, GatewayError> {
+        self.retry(|| self.inner.tx_status(tx)).await
+    }
+
+    async fn balance(&self, addr: &Address) -> Result<Amount, GatewayError> {
+        self.retry(|| self.inner.balance(addr)).await
+    }
+
+    async fn send(&self, from: &str, to: &Address, amount: Amount) -> Result<TxId, GatewayError> {
+        self.retry(|| self.inner.send(from, to, amount.clone())).await
+    }
+
+    async fn next_nonce(&self, from: &str) -> Result<Option<u64>, GatewayError> {
+        self.retry(|| self.inner.next_nonce(from)).await
+    }
+
+    async fn send_with_nonce(
+        &self,
+        from: &str,
+        to: &Address,
+        amount: Amount,
+        nonce: Option<u64>,
+    ) -> Result<TxId, GatewayError> {
+        self.retry(|| self.inner.send_with_nonce(from, to, amount.clone(), nonce)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex as StdMutex;
+
+    struct FakeConnector {
+        calls: AtomicUsize,
+        results: StdMutex<VecDeque<Result<bool, GatewayError>>>,
+    }
+
+    impl FakeConnector {
+        fn new(results: Vec<Result<bool, GatewayError>>) -> Self {
+            Self { calls: AtomicUsize::new(0), results: StdMutex::new(results.into()) }
+        }
+    }
+
+    #[async_trait]
+    impl Connector for FakeConnector {
+        fn currency(&self) -> Currency {
+            Currency::BTC
+        }
+        async fn validate_address(&self, _addr: &str) -> Result<bool, GatewayError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.results.lock().unwrap().pop_front().expect("no more results configured")
+        }
+        async fn new_deposit_address(&self) -> Result<Address, GatewayError> {
+            Ok(Address { address: "addr".into(), currency: Currency::BTC })
+        }
+        async fn create_payment_request(&self, _amount: Amount) -> Result<(Address, String), GatewayError> {
+            Ok((Address { address: "addr".into(), currency: Currency::BTC }, "inv".into()))
+        }
+        async fn tx_status(&self, _tx: &TxId) -> Result<TxStatus, GatewayError> {
+            Ok(TxStatus::Pending)
+        }
+        async fn balance(&self, _addr: &Address) -> Result<Amount, GatewayError> {
+            Ok(Amount { value: 0.0, currency: Currency::BTC })
+        }
+        async fn send(&self, _from: &str, _to: &Address, _amount: Amount) -> Result<TxId, GatewayError> {
+            Ok(TxId("tx".into()))
+        }
+        async fn next_nonce(&self, _from: &str) -> Result<Option<u64>, GatewayError> {
+            Ok(Some(7))
+        }
+        async fn send_with_nonce(
+            &self,
+            _from: &str,
+            _to: &Address,
+            _amount: Amount,
+            nonce: Option<u64>,
+        ) -> Result<TxId, GatewayError> {
+            Ok(TxId(format!("tx-nonce-{}", nonce.expect("nonce should be forwarded"))))
+        }
+    }
+
+    #[tokio::test]
+    async fn delegates_next_nonce_and_send_with_nonce_to_inner() {
+        let inner = Arc::new(FakeConnector::new(vec![]));
+        let retry = RetryConnector::new(inner.clone()).with_base_delay(Duration::from_millis(1));
+
+        assert_eq!(retry.next_nonce("0xabc").await.unwrap(), Some(7));
+
+        let to = Address { address: "dest".into(), currency: Currency::BTC };
+        let tx = retry
+            .send_with_nonce("0xabc", &to, Amount { value: 1.0, currency: Currency::BTC }, Some(42))
+            .await
+            .unwrap();
+        assert_eq!(tx.0, "tx-nonce-42");
+    }
+
+    #[tokio::test]
+    async fn retries_network_errors_until_success() {
+        let inner = Arc::new(FakeConnector::new(vec![
+            Err(GatewayError::Network("timeout".into())),
+            Err(GatewayError::Network("timeout".into())),
+            Ok(true),
+        ]));
+        let retry = RetryConnector::new(inner.clone()).with_base_delay(Duration::from_millis(1));
+
+        let result = retry.validate_address("0xabc").await;
+        assert!(result.unwrap());
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let inner = Arc::new(FakeConnector::new(vec![
+            Err(GatewayError::Network("timeout".into())),
+            Err(GatewayError::Network("timeout".into())),
+        ]));
+        let retry = RetryConnector::new(inner.clone())
+            .with_max_attempts(2)
+            .with_base_delay(Duration::from_millis(1));
+
+        let result = retry.validate_address("0xabc").await;
+        assert!(matches!(result, Err(GatewayError::Network(_))));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn non_network_errors_are_not_retried() {
+        let inner = Arc::new(FakeConnector::new(vec![Err(GatewayError::InvalidAddress("bad".into()))]));
+        let retry = RetryConnector::new(inner.clone()).with_base_delay(Duration::from_millis(1));
+
+        let result = retry.validate_address("0xabc").await;
+        assert!(matches!(result, Err(GatewayError::InvalidAddress(_))));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,23 +1,34 @@
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
+    http::StatusCode,
     routing::{get, post},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
 use tracing::{info, Level};
-use utoipa::{OpenApi, ToSchema};
+use utoipa::{IntoParams, OpenApi, ToSchema};
 use utoipa_swagger_ui::SwaggerUi;
 
-use common::{Address, ClientId, Currency};
+use common::{Address, Amount, ClientId, Connector, Currency};
+use connector_eth::EthConnector;
+use connector_middleware::{LoggingConnector, NonceManagerConnector, RetryConnector};
 use connector_mock::MockConnector; // NOTE: module name uses underscore
-use gateway_core::{FeeConfig, FeeEngine, Gateway, Registry};
+use gateway_core::{
+    FeeConfig, FeeEngine, Gateway, InMemoryEventualityStore, PayoutId, PayoutRequest,
+    PayoutStatus, Registry, Scheduler, StaticGasOracle,
+};
+use invoice_bolt11::Bolt11Invoice;
+
+/// bech32 human-readable prefix this gateway encodes Lightning invoices under.
+const LN_NETWORK_PREFIX: &str = "lnbc";
 
 #[derive(Clone)]
 struct AppState {
     gw: Arc<Gateway>,
+    scheduler: Arc<Scheduler>,
     started_at: Instant,
     version: &'static str,
 }
@@ -35,7 +46,9 @@ struct CreateInvoiceRes {
     invoice_id: String,
     fee_percent: f64,
     fee_amount: f64,
+    network_fee_estimate: f64,
     total_payable: f64,
+    payment_request: Option<String>,
 }
 
 #[utoipa::path(
@@ -50,23 +63,73 @@ async fn create_invoice(
     State(st): State<AppState>,
     Json(req): Json<CreateInvoiceReq>,
 ) -> Json<CreateInvoiceRes> {
+    let currency = req.currency;
     let client = ClientId(req.client_id);
     let (address, invoice_id, fee) = st
         .gw
-        .create_invoice(client, req.currency, req.amount)
+        .create_invoice(client, currency, req.amount)
         .await
         .expect("create_invoice");
 
-    let total = req.amount + fee.fee_amount;
+    let payment_request = (currency == Currency::LN).then(|| {
+        let invoice = Bolt11Invoice {
+            amount_msat: Some((req.amount * 1e11).round() as u64),
+            payment_hash: Bolt11Invoice::payment_hash_from_id(&invoice_id),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            expiry_secs: invoice_bolt11::DEFAULT_EXPIRY_SECS,
+        };
+        invoice.encode(LN_NETWORK_PREFIX)
+    });
+
+    let total = req.amount + fee.fee_amount + fee.network_fee_estimate;
     Json(CreateInvoiceRes {
         address,
         invoice_id,
         fee_percent: fee.percent,
         fee_amount: fee.fee_amount,
+        network_fee_estimate: fee.network_fee_estimate,
         total_payable: total,
+        payment_request,
     })
 }
 
+#[derive(Deserialize, ToSchema)]
+struct DecodeInvoiceReq {
+    payment_request: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct DecodeInvoiceRes {
+    amount_msat: Option<u64>,
+    payment_hash: String,
+    timestamp: u64,
+    expiry_secs: u64,
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/invoices/decode",
+    request_body = DecodeInvoiceReq,
+    responses(
+        (status = 200, description = "Decoded BOLT11-style invoice", body = DecodeInvoiceRes),
+        (status = 400, description = "Malformed or bad-checksum payment request")
+    )
+)]
+async fn decode_invoice(
+    Json(req): Json<DecodeInvoiceReq>,
+) -> Result<Json<DecodeInvoiceRes>, StatusCode> {
+    let invoice = Bolt11Invoice::decode(&req.payment_request).map_err(|_| StatusCode::BAD_REQUEST)?;
+    Ok(Json(DecodeInvoiceRes {
+        amount_msat: invoice.amount_msat,
+        payment_hash: hex::encode(invoice.payment_hash),
+        timestamp: invoice.timestamp,
+        expiry_secs: invoice.expiry_secs,
+    }))
+}
+
 #[derive(Deserialize, ToSchema)]
 struct FeePreviewReq {
     client_id: String,
@@ -99,6 +162,108 @@ async fn fee_preview(
     })
 }
 
+#[derive(Deserialize, ToSchema)]
+struct CreatePayoutReq {
+    from: String,
+    destination: Address,
+    amount: Amount,
+}
+
+#[derive(Serialize, ToSchema)]
+struct CreatePayoutRes {
+    payout_id: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/payouts",
+    request_body = CreatePayoutReq,
+    responses((status = 200, description = "Payout scheduled", body = CreatePayoutRes))
+)]
+async fn create_payout(
+    State(st): State<AppState>,
+    Json(req): Json<CreatePayoutReq>,
+) -> Json<CreatePayoutRes> {
+    let payout = PayoutRequest { from: req.from, destination: req.destination, amount: req.amount };
+    let id = st.scheduler.schedule(payout).await.expect("schedule payout");
+    Json(CreatePayoutRes { payout_id: id.0 })
+}
+
+#[derive(Serialize, ToSchema)]
+struct PayoutStatusRes {
+    id: String,
+    currency: Currency,
+    destination: Address,
+    amount: Amount,
+    tx_id: String,
+    state: String,
+}
+
+fn payout_status_to_res(status: PayoutStatus) -> PayoutStatusRes {
+    let state = match status.state {
+        gateway_core::PayoutState::Submitted => "submitted".to_string(),
+        gateway_core::PayoutState::Settled { confirmations } => {
+            format!("settled ({confirmations} confirmations)")
+        }
+        gateway_core::PayoutState::Failed(reason) => format!("failed: {reason}"),
+    };
+    PayoutStatusRes {
+        id: status.id.0,
+        currency: status.currency,
+        destination: status.eventuality.destination,
+        amount: status.eventuality.amount,
+        tx_id: status.eventuality.tx.0,
+        state,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/payouts/{id}",
+    params(("id" = String, Path, description = "Payout id returned by POST /v1/payouts")),
+    responses(
+        (status = 200, description = "Payout scheduling/settlement state", body = PayoutStatusRes),
+        (status = 404, description = "Unknown payout id")
+    )
+)]
+async fn get_payout(
+    State(st): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<PayoutStatusRes>, StatusCode> {
+    let status = st.scheduler.status(&PayoutId(id)).await.ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(payout_status_to_res(status)))
+}
+
+#[derive(Deserialize, IntoParams)]
+struct BalanceQuery {
+    currency: Currency,
+    address: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct BalanceRes {
+    address: Address,
+    value: f64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/balance",
+    params(BalanceQuery),
+    responses(
+        (status = 200, description = "Current on-chain balance for an address", body = BalanceRes),
+        (status = 502, description = "Connector/network error fetching the balance")
+    )
+)]
+async fn get_balance(
+    State(st): State<AppState>,
+    Query(q): Query<BalanceQuery>,
+) -> Result<Json<BalanceRes>, StatusCode> {
+    let addr = Address { address: q.address, currency: q.currency };
+    let amount = st.gw.balance(&addr).await.map_err(|_| StatusCode::BAD_GATEWAY)?;
+    Ok(Json(BalanceRes { address: addr, value: amount.value }))
+}
+
 #[derive(Serialize, ToSchema)]
 struct HealthRes {
     service: &'static str,
@@ -123,15 +288,22 @@ async fn health(State(st): State<AppState>) -> Json<HealthRes> {
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(create_invoice, fee_preview, health),
+    paths(create_invoice, fee_preview, create_payout, get_payout, get_balance, decode_invoice, health),
     components(
         schemas(
             CreateInvoiceReq,
             CreateInvoiceRes,
             FeePreviewReq,
             FeePreviewRes,
+            CreatePayoutReq,
+            CreatePayoutRes,
+            PayoutStatusRes,
+            BalanceRes,
+            DecodeInvoiceReq,
+            DecodeInvoiceRes,
             HealthRes,
             Address,
+            Amount,
             Currency
         )
     )
@@ -149,21 +321,52 @@ async fn main() {
         .with_env_filter(std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()))
         .init();
 
-    // Registry with 5 mock connectors (BTC/ETH/SOL/SUI/XRP)
+    // ETH is backed by a real `EthConnector` (wrapped as nonce manager -> retry ->
+    // logging -> connector) whenever an RPC endpoint is configured; otherwise it falls
+    // back to the mock like every other currency below.
+    let eth_connector: Arc<dyn Connector> = match std::env::var("ETH_RPC_URL") {
+        Ok(rpc_url) => {
+            let eth = EthConnector::new(&rpc_url).expect("ETH_RPC_URL should be a valid JSON-RPC endpoint");
+            Arc::new(NonceManagerConnector::new(Arc::new(RetryConnector::new(Arc::new(
+                LoggingConnector::new(Arc::new(eth)),
+            )))))
+        }
+        Err(_) => Arc::new(MockConnector { cur: Currency::ETH }),
+    };
+
+    // Registry with 6 connectors (BTC/SOL/SUI/XRP/LN mocked; ETH real when configured)
     let reg = Registry::new()
         .with(Arc::new(MockConnector { cur: Currency::BTC }))
-        .with(Arc::new(MockConnector { cur: Currency::ETH }))
+        .with(eth_connector)
         .with(Arc::new(MockConnector { cur: Currency::SOL }))
         .with(Arc::new(MockConnector { cur: Currency::SUI }))
-        .with(Arc::new(MockConnector { cur: Currency::XRP }));
+        .with(Arc::new(MockConnector { cur: Currency::XRP }))
+        .with(Arc::new(MockConnector { cur: Currency::LN }));
 
     // Fee tiers from ENV: FEE_TIERS="0:0.005,100:0.004,1000:0.003,10000:0.002,100000:0.001"
     let fee_cfg = FeeConfig::from_env();
-    let fee_engine = FeeEngine::new(fee_cfg);
+
+    // Fallback network-fee estimates so `network_fee_estimate` reflects something even
+    // without a live chain data source (e.g. `connector_eth::EthFeeHistoryOracle`)
+    // wired in; swap/override per currency as real oracles become available.
+    let gas_oracle = Arc::new(
+        StaticGasOracle::new()
+            .with_estimate(Currency::BTC, 0.0001)
+            .with_estimate(Currency::ETH, 0.0005)
+            .with_estimate(Currency::SOL, 0.000005)
+            .with_estimate(Currency::SUI, 0.000001)
+            .with_estimate(Currency::XRP, 0.00001)
+            .with_estimate(Currency::LN, 0.000001),
+    );
+    let fee_engine = FeeEngine::new(fee_cfg).with_gas_oracle(gas_oracle);
+
+    let scheduler = Arc::new(Scheduler::new(reg.clone(), Arc::new(InMemoryEventualityStore::default())));
+    scheduler.clone().spawn_poller(Duration::from_secs(30));
 
     let gw = Arc::new(Gateway::new(reg, fee_engine));
     let state = AppState {
         gw,
+        scheduler,
         started_at: Instant::now(),
         version: env!("CARGO_PKG_VERSION"),
     };
@@ -172,6 +375,10 @@ async fn main() {
         .route("/health", get(health))
         .route("/v1/invoices", post(create_invoice))
         .route("/v1/fees/preview", post(fee_preview))
+        .route("/v1/payouts", post(create_payout))
+        .route("/v1/payouts/:id", get(get_payout))
+        .route("/v1/balance", get(get_balance))
+        .route("/v1/invoices/decode", post(decode_invoice))
         .merge(SwaggerUi::new("/docs").url("/api-doc/openapi.json", ApiDoc::openapi()))
         .with_state(state);
 
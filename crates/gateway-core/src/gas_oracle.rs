@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use common::{Amount, Currency, GasOracle, GatewayError};
+use parking_lot::RwLock;
+
+/// Fallback oracle returning a fixed per-currency estimate; useful in tests/dev or as
+/// the tail of a chain when no live chain data source is configured.
+#[derive(Default)]
+pub struct StaticGasOracle {
+    estimates: HashMap<Currency, f64>,
+}
+
+impl StaticGasOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_estimate(mut self, currency: Currency, value: f64) -> Self {
+        self.estimates.insert(currency, value);
+        self
+    }
+}
+
+#[async_trait]
+impl GasOracle for StaticGasOracle {
+    async fn estimate(&self, currency: Currency) -> Result<Amount, GatewayError> {
+        let value = self
+            .estimates
+            .get(&currency)
+            .copied()
+            .ok_or_else(|| GatewayError::Unknown(format!("no static gas estimate configured for {:?}", currency)))?;
+        Ok(Amount { value, currency })
+    }
+}
+
+/// Wraps an inner `GasOracle` and caches its estimate per currency for `ttl`, so a busy
+/// invoicing endpoint doesn't hammer the RPC on every request.
+pub struct CachedGasOracle {
+    inner: Arc<dyn GasOracle>,
+    ttl: Duration,
+    cache: RwLock<HashMap<Currency, (Instant, Amount)>>,
+}
+
+impl CachedGasOracle {
+    pub fn new(inner: Arc<dyn GasOracle>, ttl: Duration) -> Self {
+        Self { inner, ttl, cache: RwLock::new(HashMap::new()) }
+    }
+}
+
+#[async_trait]
+impl GasOracle for CachedGasOracle {
+    async fn estimate(&self, currency: Currency) -> Result<Amount, GatewayError> {
+        if let Some((fetched_at, amount)) = self.cache.read().get(&currency) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(amount.clone());
+            }
+        }
+        let amount = self.inner.estimate(currency).await?;
+        self.cache.write().insert(currency, (Instant::now(), amount.clone()));
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[tokio::test]
+    async fn static_oracle_returns_configured_estimate() {
+        let oracle = StaticGasOracle::new().with_estimate(Currency::BTC, 0.0001);
+        let amount = oracle.estimate(Currency::BTC).await.unwrap();
+        assert_eq!(amount.value, 0.0001);
+        assert_eq!(amount.currency, Currency::BTC);
+    }
+
+    #[tokio::test]
+    async fn static_oracle_errors_for_unconfigured_currency() {
+        let oracle = StaticGasOracle::new().with_estimate(Currency::BTC, 0.0001);
+        assert!(oracle.estimate(Currency::ETH).await.is_err());
+    }
+
+    /// Counts calls so tests can assert the cache actually avoided re-querying `inner`.
+    struct CountingOracle {
+        calls: AtomicU64,
+    }
+
+    #[async_trait]
+    impl GasOracle for CountingOracle {
+        async fn estimate(&self, currency: Currency) -> Result<Amount, GatewayError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Amount { value: 1.0, currency })
+        }
+    }
+
+    #[tokio::test]
+    async fn cached_oracle_reuses_estimate_within_ttl() {
+        let inner = Arc::new(CountingOracle { calls: AtomicU64::new(0) });
+        let cached = CachedGasOracle::new(inner.clone(), Duration::from_secs(60));
+
+        cached.estimate(Currency::ETH).await.unwrap();
+        cached.estimate(Currency::ETH).await.unwrap();
+        cached.estimate(Currency::ETH).await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn cached_oracle_refetches_after_ttl_expires() {
+        let inner = Arc::new(CountingOracle { calls: AtomicU64::new(0) });
+        let cached = CachedGasOracle::new(inner.clone(), Duration::from_millis(1));
+
+        cached.estimate(Currency::ETH).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        cached.estimate(Currency::ETH).await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn cached_oracle_tracks_each_currency_independently() {
+        let inner = Arc::new(CountingOracle { calls: AtomicU64::new(0) });
+        let cached = CachedGasOracle::new(inner.clone(), Duration::from_secs(60));
+
+        cached.estimate(Currency::BTC).await.unwrap();
+        cached.estimate(Currency::ETH).await.unwrap();
+        cached.estimate(Currency::BTC).await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
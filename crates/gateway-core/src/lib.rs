@@ -1,5 +1,13 @@
 use std::{collections::HashMap, sync::Arc, time::{Duration, SystemTime}};
-use common::{Connector, Currency, Amount, Address, TxId, TxStatus, GatewayError, ClientId, AppliedFee};
+use common::{Connector, Currency, Amount, Address, TxId, TxStatus, GatewayError, ClientId, AppliedFee, GasOracle};
+
+mod gas_oracle;
+mod scheduler;
+pub use gas_oracle::{CachedGasOracle, StaticGasOracle};
+pub use scheduler::{
+    EventualityStore, Eventuality, InMemoryEventualityStore, PayoutId, PayoutRequest,
+    PayoutState, PayoutStatus, Scheduler,
+};
 
 #[derive(Clone)]
 pub struct FeeTier {
@@ -45,11 +53,18 @@ impl FeeConfig {
 pub struct FeeEngine {
     cfg: FeeConfig,
     counts_30d: Arc<parking_lot::RwLock<HashMap<ClientId, Vec<SystemTime>>>>,
+    gas_oracle: Option<Arc<dyn GasOracle>>,
 }
 
 impl FeeEngine {
     pub fn new(cfg: FeeConfig) -> Self {
-        Self { cfg, counts_30d: Arc::new(parking_lot::RwLock::new(HashMap::new())) }
+        Self { cfg, counts_30d: Arc::new(parking_lot::RwLock::new(HashMap::new())), gas_oracle: None }
+    }
+    /// Attaches a `GasOracle` so `fee_for_currency` folds its network-fee estimate into
+    /// the applied fee. Wrap it in a `CachedGasOracle` to avoid hammering the RPC.
+    pub fn with_gas_oracle(mut self, oracle: Arc<dyn GasOracle>) -> Self {
+        self.gas_oracle = Some(oracle);
+        self
     }
     pub fn record_tx(&self, client: &ClientId) {
         let mut map = self.counts_30d.write();
@@ -67,6 +82,8 @@ impl FeeEngine {
         Self::prune_older_than(entries, Duration::from_secs(30*24*3600));
         entries.len() as u64
     }
+    /// Tier-percentage fee only; doesn't consult the gas oracle (no currency to key it
+    /// on). Used for quick fee previews.
     pub fn fee_for(&self, client: &ClientId, amount_value: f64) -> AppliedFee {
         let count = self.current_count_30d(client);
         let mut chosen = self.cfg.tiers.first().expect("tiers");
@@ -74,7 +91,26 @@ impl FeeEngine {
             if count >= t.min_tx_count_30d { chosen = t; } else { break; }
         }
         let fee_amt = (amount_value * chosen.percent).max(0.0);
-        AppliedFee { percent: chosen.percent, fee_amount: fee_amt }
+        AppliedFee { percent: chosen.percent, fee_amount: fee_amt, network_fee_estimate: 0.0 }
+    }
+    /// Combines the tier-percentage fee with the configured `GasOracle`'s estimate for
+    /// `currency`, so callers quoting a real send see both costs. A missing oracle, or
+    /// one that doesn't cover `currency` (e.g. an ETH-only oracle queried for BTC),
+    /// leaves `network_fee_estimate` at zero rather than failing invoice creation.
+    pub async fn fee_for_currency(
+        &self,
+        client: &ClientId,
+        amount_value: f64,
+        currency: Currency,
+    ) -> Result<AppliedFee, GatewayError> {
+        let mut fee = self.fee_for(client, amount_value);
+        if let Some(oracle) = &self.gas_oracle {
+            match oracle.estimate(currency).await {
+                Ok(estimate) => fee.network_fee_estimate = estimate.value,
+                Err(e) => tracing::warn!(error = %e, ?currency, "gas oracle estimate unavailable, quoting without it"),
+            }
+        }
+        Ok(fee)
     }
 }
 
@@ -97,7 +133,7 @@ impl Gateway {
     pub async fn create_invoice(&self, client: ClientId, cur: Currency, amount: f64)
         -> Result<(Address, String, AppliedFee), GatewayError> {
         let connector = self.reg.get(cur)?;
-        let fee = self.fee.fee_for(&client, amount);
+        let fee = self.fee.fee_for_currency(&client, amount, cur).await?;
         self.fee.record_tx(&client);
         let (addr, invoice_id) = connector.create_payment_request(Amount{ value: amount, currency: cur }).await?;
         Ok((addr, invoice_id, fee))
@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common::{Address, Amount, Connector, Currency, GatewayError, TxId, TxStatus};
+use parking_lot::RwLock;
+
+use crate::Registry;
+
+/// Identifies a single scheduled payout across its lifetime.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct PayoutId(pub String);
+
+/// A payout to submit, before anything has been sent on-chain.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PayoutRequest {
+    pub from: String,
+    pub destination: Address,
+    pub amount: Amount,
+}
+
+/// The expected completion criterion for a submitted payout: the `Scheduler` considers
+/// it settled once `Connector::tx_status(tx)` reports `Confirmed(n)` with `n` at least
+/// the configured confirmation depth.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Eventuality {
+    pub destination: Address,
+    pub amount: Amount,
+    pub tx: TxId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PayoutState {
+    Submitted,
+    Settled { confirmations: u32 },
+    Failed(String),
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PayoutStatus {
+    pub id: PayoutId,
+    pub currency: Currency,
+    pub eventuality: Eventuality,
+    pub state: PayoutState,
+}
+
+/// Pluggable persistence for open and settled payouts. `InMemoryEventualityStore` is
+/// enough for a single-process gateway; a durable store (e.g. backed by Postgres) can
+/// implement this trait without the `Scheduler` itself changing.
+#[async_trait]
+pub trait EventualityStore: Send + Sync {
+    async fn insert(&self, status: PayoutStatus);
+    async fn update(&self, id: &PayoutId, state: PayoutState);
+    async fn get(&self, id: &PayoutId) -> Option<PayoutStatus>;
+    /// All payouts still awaiting settlement, i.e. in `PayoutState::Submitted`.
+    async fn open(&self) -> Vec<PayoutStatus>;
+}
+
+#[derive(Default)]
+pub struct InMemoryEventualityStore {
+    inner: RwLock<HashMap<PayoutId, PayoutStatus>>,
+}
+
+#[async_trait]
+impl EventualityStore for InMemoryEventualityStore {
+    async fn insert(&self, status: PayoutStatus) {
+        self.inner.write().insert(status.id.clone(), status);
+    }
+
+    async fn update(&self, id: &PayoutId, state: PayoutState) {
+        if let Some(status) = self.inner.write().get_mut(id) {
+            status.state = state;
+        }
+    }
+
+    async fn get(&self, id: &PayoutId) -> Option<PayoutStatus> {
+        self.inner.read().get(id).cloned()
+    }
+
+    async fn open(&self) -> Vec<PayoutStatus> {
+        self.inner
+            .read()
+            .values()
+            .filter(|s| matches!(s.state, PayoutState::Submitted))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Batches outbound payouts per `Currency` and tracks each submitted send as an
+/// `Eventuality` until it settles. Modeled on Serai's Scheduler/Eventuality split:
+/// submission and settlement-tracking are separate concerns, connected only through the
+/// `EventualityStore`.
+///
+/// `schedule()` only enqueues a payout; it's `flush_batch()`/`flush_all_batches()` that
+/// actually submit a currency's queued payouts together. "Batch" here means grouped
+/// submission timing, not a single combined on-chain transaction — `Connector::send`
+/// only models one destination per call, so each queued payout still becomes its own
+/// transaction once its currency's batch is flushed.
+pub struct Scheduler {
+    reg: Registry,
+    store: Arc<dyn EventualityStore>,
+    confirmation_depth: u32,
+    pending: RwLock<HashMap<Currency, Vec<(PayoutId, PayoutRequest)>>>,
+}
+
+impl Scheduler {
+    pub fn new(reg: Registry, store: Arc<dyn EventualityStore>) -> Self {
+        Self { reg, store, confirmation_depth: 6, pending: RwLock::new(HashMap::new()) }
+    }
+
+    pub fn with_confirmation_depth(mut self, depth: u32) -> Self {
+        self.confirmation_depth = depth;
+        self
+    }
+
+    /// Queues a payout for its destination's currency and returns its id immediately;
+    /// the payout isn't submitted on-chain until that currency's batch is flushed.
+    pub async fn schedule(&self, req: PayoutRequest) -> Result<PayoutId, GatewayError> {
+        let currency = req.destination.currency;
+        let id = PayoutId(uuid::Uuid::new_v4().to_string());
+        self.pending.write().entry(currency).or_default().push((id.clone(), req));
+        Ok(id)
+    }
+
+    /// Submits every payout currently queued for `currency` through its registry
+    /// connector, recording a successful send's eventuality as open. A failed send is
+    /// logged and dropped from the batch rather than left stuck in the queue.
+    pub async fn flush_batch(&self, currency: Currency) -> Result<(), GatewayError> {
+        let batch = self.pending.write().remove(&currency).unwrap_or_default();
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let connector = self.reg.get(currency)?;
+        for (id, req) in batch {
+            match connector.send(&req.from, &req.destination, req.amount.clone()).await {
+                Ok(tx) => {
+                    let status = PayoutStatus {
+                        id,
+                        currency,
+                        eventuality: Eventuality { destination: req.destination, amount: req.amount, tx },
+                        state: PayoutState::Submitted,
+                    };
+                    self.store.insert(status).await;
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, payout_id = %id.0, ?currency, "batched payout submission failed");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes every currency with a non-empty batch queued.
+    pub async fn flush_all_batches(&self) {
+        let currencies: Vec<Currency> = self.pending.read().keys().copied().collect();
+        for currency in currencies {
+            if let Err(e) = self.flush_batch(currency).await {
+                tracing::warn!(error = %e, ?currency, "failed to flush payout batch");
+            }
+        }
+    }
+
+    pub async fn status(&self, id: &PayoutId) -> Option<PayoutStatus> {
+        self.store.get(id).await
+    }
+
+    /// Polls every open eventuality once, settling those that have reached
+    /// `confirmation_depth` confirmations and flagging failed sends so they can be
+    /// re-queued by the caller.
+    ///
+    /// A connector hiccup on one eventuality (e.g. a transient `Network` error) is
+    /// logged and skipped rather than aborting the whole tick — otherwise one flaky
+    /// currency's RPC would starve every other open eventuality, including unrelated
+    /// currencies, until the next poll.
+    pub async fn poll_once(&self) -> Result<(), GatewayError> {
+        for status in self.store.open().await {
+            let connector = match self.reg.get(status.currency) {
+                Ok(connector) => connector,
+                Err(e) => {
+                    tracing::warn!(error = %e, payout_id = %status.id.0, "no connector for payout currency, skipping");
+                    continue;
+                }
+            };
+            match connector.tx_status(&status.eventuality.tx).await {
+                Ok(TxStatus::Confirmed(n)) if n >= self.confirmation_depth => {
+                    self.store.update(&status.id, PayoutState::Settled { confirmations: n }).await;
+                }
+                Ok(TxStatus::Failed(reason)) => {
+                    self.store.update(&status.id, PayoutState::Failed(reason)).await;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(error = %e, payout_id = %status.id.0, "tx_status poll failed, will retry next tick");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Spawns a background task that flushes every currency's pending batch and then
+    /// calls `poll_once`, on `interval`, until the returned handle is aborted.
+    pub fn spawn_poller(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.flush_all_batches().await;
+                if let Err(e) = self.poll_once().await {
+                    tracing::warn!(error = %e, "payout scheduler poll failed");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    struct FakeConnector {
+        currency: Currency,
+        tx_status_result: StdMutex<Option<Result<TxStatus, GatewayError>>>,
+    }
+
+    impl FakeConnector {
+        fn new(currency: Currency, result: Result<TxStatus, GatewayError>) -> Self {
+            Self { currency, tx_status_result: StdMutex::new(Some(result)) }
+        }
+    }
+
+    #[async_trait]
+    impl Connector for FakeConnector {
+        fn currency(&self) -> Currency {
+            self.currency
+        }
+        async fn validate_address(&self, _addr: &str) -> Result<bool, GatewayError> {
+            Ok(true)
+        }
+        async fn new_deposit_address(&self) -> Result<Address, GatewayError> {
+            Ok(Address { address: "addr".into(), currency: self.currency })
+        }
+        async fn create_payment_request(&self, _amount: Amount) -> Result<(Address, String), GatewayError> {
+            Ok((Address { address: "addr".into(), currency: self.currency }, "inv".into()))
+        }
+        async fn tx_status(&self, _tx: &TxId) -> Result<TxStatus, GatewayError> {
+            self.tx_status_result.lock().unwrap().take().expect("tx_status called more than once in this test")
+        }
+        async fn balance(&self, _addr: &Address) -> Result<Amount, GatewayError> {
+            Ok(Amount { value: 0.0, currency: self.currency })
+        }
+        async fn send(&self, _from: &str, _to: &Address, _amount: Amount) -> Result<TxId, GatewayError> {
+            Ok(TxId("tx".into()))
+        }
+    }
+
+    fn submitted(currency: Currency, id: &str) -> PayoutStatus {
+        PayoutStatus {
+            id: PayoutId(id.to_string()),
+            currency,
+            eventuality: Eventuality {
+                destination: Address { address: "dest".into(), currency },
+                amount: Amount { value: 1.0, currency },
+                tx: TxId("tx".into()),
+            },
+            state: PayoutState::Submitted,
+        }
+    }
+
+    #[tokio::test]
+    async fn one_currency_erroring_does_not_block_others() {
+        let reg = Registry::new()
+            .with(Arc::new(FakeConnector::new(Currency::BTC, Ok(TxStatus::Confirmed(10)))))
+            .with(Arc::new(FakeConnector::new(Currency::ETH, Err(GatewayError::Network("rpc down".into())))));
+        let store = Arc::new(InMemoryEventualityStore::default());
+        store.insert(submitted(Currency::BTC, "btc-payout")).await;
+        store.insert(submitted(Currency::ETH, "eth-payout")).await;
+
+        let scheduler = Scheduler::new(reg, store.clone());
+        scheduler.poll_once().await.expect("poll_once should not bail on a single connector error");
+
+        let btc_status = store.get(&PayoutId("btc-payout".into())).await.unwrap();
+        assert_eq!(btc_status.state, PayoutState::Settled { confirmations: 10 });
+
+        let eth_status = store.get(&PayoutId("eth-payout".into())).await.unwrap();
+        assert_eq!(eth_status.state, PayoutState::Submitted);
+    }
+
+    fn payout(currency: Currency) -> PayoutRequest {
+        PayoutRequest {
+            from: "from".into(),
+            destination: Address { address: "dest".into(), currency },
+            amount: Amount { value: 1.0, currency },
+        }
+    }
+
+    #[tokio::test]
+    async fn schedule_queues_without_submitting_until_flushed() {
+        let reg = Registry::new().with(Arc::new(FakeConnector::new(Currency::BTC, Ok(TxStatus::Confirmed(10)))));
+        let store = Arc::new(InMemoryEventualityStore::default());
+        let scheduler = Scheduler::new(reg, store.clone());
+
+        let id = scheduler.schedule(payout(Currency::BTC)).await.unwrap();
+        assert!(scheduler.status(&id).await.is_none(), "schedule should only enqueue, not submit");
+
+        scheduler.flush_batch(Currency::BTC).await.unwrap();
+        assert!(scheduler.status(&id).await.is_some(), "flush_batch should submit the queued payout");
+    }
+
+    #[tokio::test]
+    async fn flush_batch_submits_every_queued_payout_for_that_currency_only() {
+        let reg = Registry::new()
+            .with(Arc::new(FakeConnector::new(Currency::BTC, Ok(TxStatus::Confirmed(10)))))
+            .with(Arc::new(FakeConnector::new(Currency::ETH, Ok(TxStatus::Confirmed(10)))));
+        let store = Arc::new(InMemoryEventualityStore::default());
+        let scheduler = Scheduler::new(reg, store.clone());
+
+        let btc_one = scheduler.schedule(payout(Currency::BTC)).await.unwrap();
+        let btc_two = scheduler.schedule(payout(Currency::BTC)).await.unwrap();
+        let eth_one = scheduler.schedule(payout(Currency::ETH)).await.unwrap();
+
+        scheduler.flush_batch(Currency::BTC).await.unwrap();
+
+        assert!(scheduler.status(&btc_one).await.is_some());
+        assert!(scheduler.status(&btc_two).await.is_some());
+        assert!(scheduler.status(&eth_one).await.is_none(), "flushing BTC should not touch ETH's queue");
+    }
+}
@@ -0,0 +1,119 @@
+//! Minimal bech32 (BIP-173) codec: charset, checksum, and the generic bit-regrouping
+//! helper used to pack arbitrary byte data into 5-bit symbols and back.
+
+use common::GatewayError;
+
+pub const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn char_value(c: char) -> Option<u8> {
+    CHARSET.iter().position(|&x| x as char == c).map(|p| p as u8)
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = (chk >> 25) as u8;
+        chk = ((chk & 0x1ff_ffff) << 5) ^ (v as u32);
+        for (i, gen) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= gen;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut v: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    v.push(0);
+    v.extend(hrp.bytes().map(|b| b & 31));
+    v
+}
+
+fn create_checksum(hrp: &str, data: &[u8]) -> [u8; 6] {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; 6]);
+    let poly = polymod(&values) ^ 1;
+    let mut checksum = [0u8; 6];
+    for (i, c) in checksum.iter_mut().enumerate() {
+        *c = ((poly >> (5 * (5 - i))) & 31) as u8;
+    }
+    checksum
+}
+
+fn verify_checksum(hrp: &str, data: &[u8]) -> bool {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    polymod(&values) == 1
+}
+
+/// Encodes `data` (already split into 5-bit symbols) under human-readable part `hrp`.
+pub fn encode(hrp: &str, data: &[u8]) -> String {
+    let checksum = create_checksum(hrp, data);
+    let mut out = String::with_capacity(hrp.len() + 1 + data.len() + 6);
+    out.push_str(hrp);
+    out.push('1');
+    for &d in data.iter().chain(checksum.iter()) {
+        out.push(CHARSET[d as usize] as char);
+    }
+    out
+}
+
+/// Decodes a bech32 string, rejecting it outright if the checksum doesn't verify.
+/// Returns the human-readable part and the 5-bit symbol payload (checksum stripped).
+pub fn decode(s: &str) -> Result<(String, Vec<u8>), GatewayError> {
+    if s.chars().any(|c| c.is_ascii_uppercase()) && s.chars().any(|c| c.is_ascii_lowercase()) {
+        return Err(GatewayError::Unknown("mixed-case bech32 string".into()));
+    }
+    let lower = s.to_lowercase();
+    let sep = lower.rfind('1').ok_or_else(|| GatewayError::Unknown("missing bech32 separator".into()))?;
+    let hrp = &lower[..sep];
+    let data_part = &lower[sep + 1..];
+    if hrp.is_empty() || data_part.len() < 6 {
+        return Err(GatewayError::Unknown("bech32 string too short".into()));
+    }
+
+    let mut data = Vec::with_capacity(data_part.len());
+    for c in data_part.chars() {
+        data.push(char_value(c).ok_or_else(|| GatewayError::Unknown(format!("invalid bech32 character: {c}")))?);
+    }
+
+    if !verify_checksum(hrp, &data) {
+        return Err(GatewayError::Unknown("invalid bech32 checksum".into()));
+    }
+
+    let payload = data[..data.len() - 6].to_vec();
+    Ok((hrp.to_string(), payload))
+}
+
+/// Re-groups `data` from `from_bits`-wide to `to_bits`-wide values, e.g. bytes (8) to
+/// bech32 symbols (5) and back. With `pad`, a short final group is zero-padded; without
+/// it, a non-zero remainder is rejected as malformed input.
+pub fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let maxv: u32 = (1 << to_bits) - 1;
+    let mut ret = Vec::new();
+    for &value in data {
+        let v = value as u32;
+        if (v >> from_bits) != 0 {
+            return None;
+        }
+        acc = (acc << from_bits) | v;
+        bits += from_bits;
+        while bits >= to_bits {
+            bits -= to_bits;
+            ret.push(((acc >> bits) & maxv) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            ret.push(((acc << (to_bits - bits)) & maxv) as u8);
+        }
+    } else if bits >= from_bits || ((acc << (to_bits - bits)) & maxv) != 0 {
+        return None;
+    }
+    Some(ret)
+}
@@ -0,0 +1,216 @@
+//! BOLT11-style Lightning payment request encoding: a bech32 string with a
+//! human-readable amount prefix (e.g. `lnbc2500u`), carrying a timestamp and a handful
+//! of tagged fields (payment hash, expiry). This is deliberately a subset of real
+//! BOLT11 — there's no node signature, since this gateway doesn't run a Lightning node
+//! to sign with — just enough to quote and later recognize a Lightning invoice.
+
+mod bech32;
+
+use common::GatewayError;
+
+/// Default invoice expiry per BOLT11 when no `x` tagged field is present.
+pub const DEFAULT_EXPIRY_SECS: u64 = 3600;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bolt11Invoice {
+    pub amount_msat: Option<u64>,
+    pub payment_hash: [u8; 32],
+    pub timestamp: u64,
+    pub expiry_secs: u64,
+}
+
+impl Bolt11Invoice {
+    /// Derives a 32-byte payment hash from an opaque invoice id, so callers that only
+    /// have a `Connector`-issued invoice id can still mint a payment hash for it.
+    pub fn payment_hash_from_id(invoice_id: &str) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+        let digest = Sha256::digest(invoice_id.as_bytes());
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&digest);
+        out
+    }
+
+    /// Encodes this invoice as a BOLT11-style string under `network_prefix` (e.g.
+    /// `lnbc` for mainnet, `lntb` for testnet).
+    pub fn encode(&self, network_prefix: &str) -> String {
+        let mut hrp = network_prefix.to_string();
+        if let Some(msat) = self.amount_msat {
+            hrp.push_str(&amount_to_hrp_part(msat));
+        }
+
+        let mut data = u64_to_groups(self.timestamp, TIMESTAMP_GROUPS);
+        let payment_hash_groups =
+            bech32::convert_bits(&self.payment_hash, 8, 5, true).expect("32 bytes always convert cleanly");
+        data.extend(tagged_field(b'p', &payment_hash_groups));
+        data.extend(tagged_field(b'x', &u64_to_min_groups(self.expiry_secs)));
+
+        bech32::encode(&hrp, &data)
+    }
+
+    /// Parses a BOLT11-style string back into amount/hash/timestamp/expiry, rejecting
+    /// strings with a bad bech32 checksum or a missing payment hash.
+    pub fn decode(s: &str) -> Result<Self, GatewayError> {
+        let (hrp, data) = bech32::decode(s)?;
+        let amount_msat = parse_hrp_amount(&hrp)?;
+
+        if data.len() < TIMESTAMP_GROUPS {
+            return Err(GatewayError::Unknown("invoice missing timestamp".into()));
+        }
+        let timestamp = groups_to_u64(&data[..TIMESTAMP_GROUPS]);
+
+        let mut payment_hash = None;
+        let mut expiry_secs = DEFAULT_EXPIRY_SECS;
+
+        let mut i = TIMESTAMP_GROUPS;
+        while i + 3 <= data.len() {
+            let tag = bech32::CHARSET[data[i] as usize];
+            let len = ((data[i + 1] as usize) << 5) | (data[i + 2] as usize);
+            let start = i + 3;
+            let end = start + len;
+            if end > data.len() {
+                return Err(GatewayError::Unknown("truncated tagged field".into()));
+            }
+            let field = &data[start..end];
+            match tag {
+                b'p' => {
+                    let bytes = bech32::convert_bits(field, 5, 8, false)
+                        .ok_or_else(|| GatewayError::Unknown("malformed payment hash field".into()))?;
+                    if bytes.len() != 32 {
+                        return Err(GatewayError::Unknown("payment hash must be 32 bytes".into()));
+                    }
+                    let mut arr = [0u8; 32];
+                    arr.copy_from_slice(&bytes);
+                    payment_hash = Some(arr);
+                }
+                b'x' => expiry_secs = groups_to_u64(field),
+                _ => {} // unknown tag: skip, as BOLT11 readers must
+            }
+            i = end;
+        }
+
+        Ok(Bolt11Invoice {
+            amount_msat,
+            payment_hash: payment_hash
+                .ok_or_else(|| GatewayError::Unknown("invoice is missing its payment hash field".into()))?,
+            timestamp,
+            expiry_secs,
+        })
+    }
+}
+
+const TIMESTAMP_GROUPS: usize = 7; // 7 * 5 = 35 bits, per BOLT11
+
+fn tagged_field(tag: u8, data: &[u8]) -> Vec<u8> {
+    let tag_symbol = bech32::CHARSET.iter().position(|&c| c == tag).expect("valid tag char") as u8;
+    let len = data.len();
+    let mut out = vec![tag_symbol, (len >> 5) as u8, (len & 31) as u8];
+    out.extend_from_slice(data);
+    out
+}
+
+fn u64_to_groups(val: u64, n: usize) -> Vec<u8> {
+    (0..n).rev().map(|i| ((val >> (5 * i)) & 0x1f) as u8).collect()
+}
+
+fn u64_to_min_groups(val: u64) -> Vec<u8> {
+    if val == 0 {
+        return vec![0];
+    }
+    let mut groups = Vec::new();
+    let mut v = val;
+    while v > 0 {
+        groups.push((v & 0x1f) as u8);
+        v >>= 5;
+    }
+    groups.reverse();
+    groups
+}
+
+fn groups_to_u64(groups: &[u8]) -> u64 {
+    groups.iter().fold(0u64, |acc, &g| (acc << 5) | (g as u64))
+}
+
+/// Converts a millisatoshi amount into BOLT11's amount suffix, picking the largest
+/// unit (milli/micro/nano/pico-bitcoin) that represents it exactly.
+fn amount_to_hrp_part(msat: u64) -> String {
+    if msat % 100_000_000 == 0 {
+        format!("{}m", msat / 100_000_000)
+    } else if msat % 100_000 == 0 {
+        format!("{}u", msat / 100_000)
+    } else if msat % 100 == 0 {
+        format!("{}n", msat / 100)
+    } else {
+        format!("{}p", msat * 10)
+    }
+}
+
+fn parse_hrp_amount(hrp: &str) -> Result<Option<u64>, GatewayError> {
+    let Some(digit_start) = hrp.find(|c: char| c.is_ascii_digit()) else { return Ok(None) };
+    let amount_part = &hrp[digit_start..];
+    let (digits, unit) = amount_part.split_at(amount_part.len() - 1);
+    let n: u64 = digits.parse().map_err(|_| GatewayError::Unknown(format!("bad invoice amount: {amount_part}")))?;
+    let msat = match unit {
+        "m" => n * 100_000_000,
+        "u" => n * 100_000,
+        "n" => n * 100,
+        "p" => n / 10,
+        other => return Err(GatewayError::Unknown(format!("unknown amount unit: {other}"))),
+    };
+    Ok(Some(msat))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(amount_msat: Option<u64>) -> Bolt11Invoice {
+        Bolt11Invoice {
+            amount_msat,
+            payment_hash: Bolt11Invoice::payment_hash_from_id("inv_01HX"),
+            timestamp: 1_700_000_000,
+            expiry_secs: 900,
+        }
+    }
+
+    #[test]
+    fn round_trips_with_amount() {
+        let invoice = sample(Some(250_000_000_000)); // 2.5 BTC, divisible by the milli unit
+        let encoded = invoice.encode("lnbc");
+        assert!(encoded.starts_with("lnbc"));
+        let decoded = Bolt11Invoice::decode(&encoded).expect("decode");
+        assert_eq!(decoded, invoice);
+    }
+
+    #[test]
+    fn round_trips_without_amount() {
+        let invoice = sample(None);
+        let encoded = invoice.encode("lnbc");
+        let decoded = Bolt11Invoice::decode(&encoded).expect("decode");
+        assert_eq!(decoded, invoice);
+    }
+
+    #[test]
+    fn round_trips_an_odd_msat_amount_via_pico() {
+        let invoice = sample(Some(1_234_567));
+        let encoded = invoice.encode("lnbc");
+        let decoded = Bolt11Invoice::decode(&encoded).expect("decode");
+        assert_eq!(decoded.amount_msat, Some(1_234_567));
+    }
+
+    #[test]
+    fn rejects_bad_checksum() {
+        let mut encoded = sample(Some(100_000)).encode("lnbc");
+        let last = encoded.pop().unwrap();
+        let replacement = if last == 'q' { 'p' } else { 'q' };
+        encoded.push(replacement);
+        assert!(Bolt11Invoice::decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn rejects_invoice_missing_payment_hash() {
+        let hrp = "lnbc";
+        let data = u64_to_groups(1_700_000_000, TIMESTAMP_GROUPS);
+        let encoded = bech32::encode(hrp, &data);
+        assert!(Bolt11Invoice::decode(&encoded).is_err());
+    }
+}